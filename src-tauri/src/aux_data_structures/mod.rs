@@ -0,0 +1,2 @@
+pub mod neighbor_info;
+pub mod union_find;
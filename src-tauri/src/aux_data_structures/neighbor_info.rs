@@ -0,0 +1,15 @@
+/// A neighbor observation as reported by a node's periodic neighbor-info packet.
+#[derive(Debug, Clone)]
+pub struct Neighbor {
+    pub id: u32,
+    pub timestamp: u64,
+    pub snr: f64,
+}
+
+/// The set of neighbors a given node reported seeing as of `timestamp`.
+#[derive(Debug, Clone)]
+pub struct NeighborInfo {
+    pub id: u32,
+    pub timestamp: u64,
+    pub neighbors: Vec<Neighbor>,
+}
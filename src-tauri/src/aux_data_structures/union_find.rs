@@ -0,0 +1,61 @@
+/// A disjoint-set (union-find) structure over the integers `0..size`, with
+/// union by rank and path compression so `find`/`union` are near-constant
+/// time. Used by algorithms like Kruskal's MST that need to track which
+/// components two elements belong to.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(size: usize) -> Self {
+        UnionFind {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        }
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    /// Merges the components containing `x` and `y`. Returns `true` if they
+    /// were previously in different components (i.e. a merge happened).
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+        if root_x == root_y {
+            return false;
+        }
+
+        match self.rank[root_x].cmp(&self.rank[root_y]) {
+            std::cmp::Ordering::Less => self.parent[root_x] = root_y,
+            std::cmp::Ordering::Greater => self.parent[root_y] = root_x,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_y] = root_x;
+                self.rank[root_x] += 1;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find_merges_components() {
+        let mut uf = UnionFind::new(4);
+        assert_ne!(uf.find(0), uf.find(1));
+        assert!(uf.union(0, 1));
+        assert_eq!(uf.find(0), uf.find(1));
+        // Already merged, so a second union of the same pair is a no-op.
+        assert!(!uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert_ne!(uf.find(0), uf.find(2));
+    }
+}
@@ -0,0 +1,5 @@
+/// Meshtastic encodes latitude/longitude as fixed-point integers scaled by 1e-7
+/// degrees, and altitude as whole meters above sea level; see mesh.proto.
+pub const LAT_CONVERSION_FACTOR: f64 = 1e-7;
+pub const LON_CONVERSION_FACTOR: f64 = 1e-7;
+pub const ALT_CONVERSION_FACTOR: f64 = 1.0;
@@ -0,0 +1,57 @@
+use petgraph::graph::NodeIndex;
+
+/// A candidate link between two mesh nodes, combining the (possibly
+/// estimated) distance between them with their measured radio quality into a
+/// single routing weight. The raw `distance_m`/`snr` are kept alongside the
+/// combined `weight` so downstream consumers (e.g. GeoJSON export) can
+/// report the underlying measurements instead of just the derived cost.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub u: NodeIndex,
+    pub v: NodeIndex,
+    pub distance_m: f64,
+    pub snr: f64,
+    pub weight: f64,
+}
+
+pub const DEFAULT_DISTANCE_WEIGHT: f64 = 1.0;
+const DEFAULT_RADIO_WEIGHT: f64 = 1.0;
+
+/// Normalizes raw distances (in meters) onto a scale comparable to the
+/// [0, 1] radio-quality term, so neither term dominates the combined weight.
+pub const DISTANCE_NORMALIZATION_M: f64 = 1000.0;
+
+/// Builds routing edges from parallel vectors of endpoints, distances, and
+/// SNR readings. `distance_weight` and `radio_weight` let callers trade off
+/// how much each term contributes to the final cost; both default to 1.0.
+pub fn edge_factory(
+    left_endpoints: Vec<NodeIndex>,
+    right_endpoints: Vec<NodeIndex>,
+    distances: Vec<f64>,
+    radio_quality: Vec<f64>,
+    distance_weight: Option<f64>,
+    radio_weight: Option<f64>,
+) -> Vec<Edge> {
+    let distance_weight = distance_weight.unwrap_or(DEFAULT_DISTANCE_WEIGHT);
+    let radio_weight = radio_weight.unwrap_or(DEFAULT_RADIO_WEIGHT);
+
+    left_endpoints
+        .into_iter()
+        .zip(right_endpoints)
+        .zip(distances)
+        .zip(radio_quality)
+        .map(|(((u, v), distance), snr)| Edge {
+            u,
+            v,
+            distance_m: distance,
+            snr,
+            weight: edge_weight(distance, snr, distance_weight, radio_weight),
+        })
+        .collect()
+}
+
+fn edge_weight(distance: f64, snr: f64, distance_weight: f64, radio_weight: f64) -> f64 {
+    let normalized_distance = distance / DISTANCE_NORMALIZATION_M;
+    let radio_quality_term = 1.0 - snr.clamp(0.0, 1.0);
+    distance_weight * normalized_distance + radio_weight * radio_quality_term
+}
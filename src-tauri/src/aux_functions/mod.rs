@@ -0,0 +1,4 @@
+pub mod conversion_factors;
+pub mod edge_factory;
+pub mod path_loss;
+pub mod take_snapshot;
@@ -0,0 +1,81 @@
+/// Parameters for the log-distance path-loss model used to estimate link
+/// distance from a measured SNR when no GPS fix is available for one or
+/// both endpoints.
+#[derive(Debug, Clone, Copy)]
+pub struct PathLossParams {
+    /// Transmit power, in dBm.
+    pub tx_power_dbm: f64,
+    /// Receiver noise floor, in dBm, used to recover RSSI from SNR.
+    pub noise_floor_dbm: f64,
+    /// Path-loss exponent `n`; higher values model denser/noisier
+    /// environments. Defaults to ~2.7, typical for a suburban mesh.
+    pub path_loss_exponent: f64,
+    /// Path loss at the reference distance, in dB.
+    pub reference_loss_db: f64,
+    /// Reference distance `d0`, in meters.
+    pub reference_distance_m: f64,
+}
+
+impl Default for PathLossParams {
+    fn default() -> Self {
+        PathLossParams {
+            tx_power_dbm: 20.0,
+            noise_floor_dbm: -120.0,
+            path_loss_exponent: 2.7,
+            reference_loss_db: 40.0,
+            reference_distance_m: 1.0,
+        }
+    }
+}
+
+/// The dB SNR this model maps the `[0, 1]` link-quality score used
+/// everywhere else in this crate (see `edge_factory::edge_weight`) onto, so
+/// a "terrible" link (quality 0.0) reads as a weak dB SNR and a "perfect"
+/// link (quality 1.0) reads as a strong one.
+const MIN_SNR_DB: f64 = 0.0;
+const MAX_SNR_DB: f64 = 30.0;
+
+/// Estimates the distance between two nodes from a measured link-quality
+/// score (in the same `[0, 1]` domain as `edge_factory`'s `snr` parameter)
+/// using the log-distance path-loss model:
+///
+/// `distance = d0 * 10^((tx_power - rssi - PL(d0)) / (10 * n))`
+///
+/// where `rssi` is recovered from the quality score via the receiver's noise
+/// floor, after first mapping it onto a plausible dB SNR range.
+pub fn estimate_distance_from_snr(snr_quality: f64, params: &PathLossParams) -> f64 {
+    let rssi = snr_to_rssi(snr_quality, params.noise_floor_dbm);
+    let exponent =
+        (params.tx_power_dbm - rssi - params.reference_loss_db) / (10.0 * params.path_loss_exponent);
+    params.reference_distance_m * 10f64.powf(exponent)
+}
+
+fn snr_to_rssi(snr_quality: f64, noise_floor_dbm: f64) -> f64 {
+    let snr_db = MIN_SNR_DB + snr_quality.clamp(0.0, 1.0) * (MAX_SNR_DB - MIN_SNR_DB);
+    snr_db + noise_floor_dbm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_decreases_as_link_quality_increases() {
+        let params = PathLossParams::default();
+        let weak = estimate_distance_from_snr(0.1, &params);
+        let strong = estimate_distance_from_snr(0.9, &params);
+        assert!(
+            strong < weak,
+            "expected a stronger link (0.9) to estimate closer than a weak one (0.1), got {strong} >= {weak}"
+        );
+    }
+
+    #[test]
+    fn test_distance_is_monotonic_over_the_quality_domain() {
+        let params = PathLossParams::default();
+        let distances: Vec<f64> = (0..=10)
+            .map(|i| estimate_distance_from_snr(i as f64 / 10.0, &params))
+            .collect();
+        assert!(distances.windows(2).all(|pair| pair[1] <= pair[0]));
+    }
+}
@@ -0,0 +1,31 @@
+/// Mean radius of the earth, in meters.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Great-circle distance between two lat/long/altitude points, in meters.
+///
+/// Combines the haversine surface distance with the altitude delta via the
+/// Pythagorean theorem, which holds closely enough at the short ranges
+/// (tens of meters to a few kilometers) seen between mesh nodes.
+pub fn total_distance(
+    lat_1: f64,
+    lon_1: f64,
+    alt_1: f64,
+    lat_2: f64,
+    lon_2: f64,
+    alt_2: f64,
+) -> f64 {
+    let surface_distance = haversine_distance(lat_1, lon_1, lat_2, lon_2);
+    let alt_delta = alt_2 - alt_1;
+    (surface_distance.powi(2) + alt_delta.powi(2)).sqrt()
+}
+
+fn haversine_distance(lat_1: f64, lon_1: f64, lat_2: f64, lon_2: f64) -> f64 {
+    let lat_1 = lat_1.to_radians();
+    let lat_2 = lat_2.to_radians();
+    let d_lat = lat_2 - lat_1;
+    let d_lon = (lon_2 - lon_1).to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2) + lat_1.cos() * lat_2.cos() * (d_lon / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().asin();
+    EARTH_RADIUS_M * c
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::aux_functions::conversion_factors::{LAT_CONVERSION_FACTOR, LON_CONVERSION_FACTOR};
+use crate::graph::graph_ds::Graph;
+use crate::mesh::device::MeshNode;
+
+/// A single GeoJSON Feature: a geometry plus arbitrary properties. This
+/// mirrors just the subset of the GeoJSON spec the map frontend renders, so
+/// it can deserialize features directly without a full GeoJSON crate.
+#[derive(Debug, Clone, Serialize)]
+pub struct Feature {
+    #[serde(rename = "type")]
+    pub feature_type: &'static str,
+    pub geometry: Value,
+    pub properties: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureCollection {
+    #[serde(rename = "type")]
+    pub collection_type: &'static str,
+    pub features: Vec<Feature>,
+}
+
+/// Converts a `Graph` into a GeoJSON `FeatureCollection`: each node becomes a
+/// Point feature at its inverse-converted (i.e. decoded back from the
+/// fixed-point protobuf ints into real degrees) lat/long, and each edge
+/// becomes a LineString carrying its SNR and distance as properties. Nodes
+/// without a `MeshNode` entry or a GPS fix are skipped rather than plotted
+/// at a bogus location.
+pub fn graph_to_geojson(graph: &Graph, node_data: &HashMap<u32, MeshNode>) -> FeatureCollection {
+    let mut features = Vec::new();
+
+    for (_, name) in graph.graph.node_references() {
+        if let Some((lat, lon)) = lookup_lat_lon(name, node_data) {
+            features.push(Feature {
+                feature_type: "Feature",
+                geometry: json!({ "type": "Point", "coordinates": [lon, lat] }),
+                properties: json!({ "node_id": name }),
+            });
+        }
+    }
+
+    for edge in graph.graph.edge_references() {
+        let (Some(source_name), Some(target_name)) = (
+            graph.graph.node_weight(edge.source()),
+            graph.graph.node_weight(edge.target()),
+        ) else {
+            continue;
+        };
+        let (Some((src_lat, src_lon)), Some((dst_lat, dst_lon))) = (
+            lookup_lat_lon(source_name, node_data),
+            lookup_lat_lon(target_name, node_data),
+        ) else {
+            continue;
+        };
+        let (distance_m, snr) = graph
+            .get_link_metrics(edge.source(), edge.target())
+            .unwrap_or((0.0, 0.0));
+
+        features.push(Feature {
+            feature_type: "Feature",
+            geometry: json!({
+                "type": "LineString",
+                "coordinates": [[src_lon, src_lat], [dst_lon, dst_lat]],
+            }),
+            properties: json!({ "snr": snr, "distance_m": distance_m }),
+        });
+    }
+
+    FeatureCollection {
+        collection_type: "FeatureCollection",
+        features,
+    }
+}
+
+/// Turns a node-ID path (as returned by [`Graph::shortest_path`]) into a
+/// single LineString feature, so a computed route renders the same way as
+/// the rest of the topology. Returns `None` if any node along the route has
+/// no known position.
+pub fn route_to_geojson(route: &[String], node_data: &HashMap<u32, MeshNode>) -> Option<Feature> {
+    let coordinates = route
+        .iter()
+        .map(|name| lookup_lat_lon(name, node_data).map(|(lat, lon)| json!([lon, lat])))
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Feature {
+        feature_type: "Feature",
+        geometry: json!({ "type": "LineString", "coordinates": coordinates }),
+        properties: json!({ "node_count": route.len() }),
+    })
+}
+
+fn lookup_lat_lon(name: &str, node_data: &HashMap<u32, MeshNode>) -> Option<(f64, f64)> {
+    let node = node_data.get(&name.parse::<u32>().ok()?)?;
+    let position = node.data.position?;
+    Some((
+        position.latitude_i as f64 * LAT_CONVERSION_FACTOR,
+        position.longitude_i as f64 * LON_CONVERSION_FACTOR,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aux_functions::edge_factory::Edge;
+    use app::protobufs;
+
+    fn meshnode_at(num: u32, latitude_i: i32, longitude_i: i32) -> MeshNode {
+        MeshNode {
+            device_metrics: vec![],
+            environment_metrics: vec![],
+            data: protobufs::NodeInfo {
+                num,
+                user: None,
+                position: Some(protobufs::Position {
+                    latitude_i,
+                    longitude_i,
+                    altitude: 0,
+                    time: 0,
+                    location_source: 0,
+                    altitude_source: 0,
+                    timestamp: 0,
+                    timestamp_millis_adjust: 0,
+                    altitude_hae: 0,
+                    altitude_geoidal_separation: 0,
+                    pdop: 0,
+                    hdop: 0,
+                    vdop: 0,
+                    gps_accuracy: 0,
+                    ground_speed: 0,
+                    ground_track: 0,
+                    fix_quality: 0,
+                    fix_type: 0,
+                    sats_in_view: 0,
+                    sensor_id: 0,
+                    next_update: 0,
+                    seq_number: 0,
+                }),
+                snr: 0.0,
+                last_heard: 0,
+                device_metrics: None,
+            },
+        }
+    }
+
+    fn meshnode_without_position(num: u32) -> MeshNode {
+        MeshNode {
+            device_metrics: vec![],
+            environment_metrics: vec![],
+            data: protobufs::NodeInfo {
+                num,
+                user: None,
+                position: None,
+                snr: 0.0,
+                last_heard: 0,
+                device_metrics: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_graph_to_geojson_skips_node_and_edge_without_position() {
+        let mut graph = Graph::new();
+        graph.add_node("1".to_string());
+        graph.add_node("2".to_string());
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("1".to_string()),
+            v: graph.get_node_idx("2".to_string()),
+            distance_m: 10.0,
+            snr: 0.5,
+            weight: 1.0,
+        });
+
+        let mut node_data = HashMap::new();
+        node_data.insert(1, meshnode_at(1, 0, 0));
+        node_data.insert(2, meshnode_without_position(2));
+
+        let collection = graph_to_geojson(&graph, &node_data);
+
+        // Only node 1 has a position, so it's the only Point feature, and
+        // the 1-2 edge is skipped entirely since node 2 can't be placed.
+        let points: Vec<_> = collection
+            .features
+            .iter()
+            .filter(|f| f.geometry["type"] == "Point")
+            .collect();
+        let lines: Vec<_> = collection
+            .features
+            .iter()
+            .filter(|f| f.geometry["type"] == "LineString")
+            .collect();
+        assert_eq!(points.len(), 1);
+        assert_eq!(lines.len(), 0);
+    }
+
+    #[test]
+    fn test_graph_to_geojson_includes_edge_properties() {
+        let mut graph = Graph::new();
+        graph.add_node("1".to_string());
+        graph.add_node("2".to_string());
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("1".to_string()),
+            v: graph.get_node_idx("2".to_string()),
+            distance_m: 42.0,
+            snr: 0.75,
+            weight: 1.0,
+        });
+
+        let mut node_data = HashMap::new();
+        node_data.insert(1, meshnode_at(1, 0, 0));
+        node_data.insert(2, meshnode_at(2, 0, 0));
+
+        let collection = graph_to_geojson(&graph, &node_data);
+
+        let line = collection
+            .features
+            .iter()
+            .find(|f| f.geometry["type"] == "LineString")
+            .expect("edge feature should be present");
+        assert_eq!(line.properties["distance_m"], 42.0);
+        assert_eq!(line.properties["snr"], 0.75);
+    }
+
+    #[test]
+    fn test_route_to_geojson_returns_none_for_unpositioned_node() {
+        let mut node_data = HashMap::new();
+        node_data.insert(1, meshnode_at(1, 0, 0));
+        node_data.insert(2, meshnode_without_position(2));
+
+        let route = vec!["1".to_string(), "2".to_string()];
+        assert!(route_to_geojson(&route, &node_data).is_none());
+    }
+
+    #[test]
+    fn test_route_to_geojson_builds_linestring_for_positioned_route() {
+        let mut node_data = HashMap::new();
+        node_data.insert(1, meshnode_at(1, 0, 0));
+        node_data.insert(2, meshnode_at(2, 0, 0));
+
+        let route = vec!["1".to_string(), "2".to_string()];
+        let feature = route_to_geojson(&route, &node_data).expect("route should have a position");
+        assert_eq!(feature.geometry["coordinates"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,56 @@
+/// Precision factor for the Google polyline algorithm: coordinates are
+/// rounded to 5 decimal places (about 1.1m) before delta-encoding.
+const POLYLINE_PRECISION: f64 = 1e5;
+
+/// Encodes a sequence of (lat, long) points, in degrees, as a Google-style
+/// encoded polyline string, for compact transmission to the map frontend.
+/// See <https://developers.google.com/maps/documentation/utilities/polylinealgorithm>.
+pub fn encode_polyline(points: &[(f64, f64)]) -> String {
+    let mut encoded = String::new();
+    let mut prev_lat = 0i64;
+    let mut prev_lon = 0i64;
+
+    for &(lat, lon) in points {
+        let lat = (lat * POLYLINE_PRECISION).round() as i64;
+        let lon = (lon * POLYLINE_PRECISION).round() as i64;
+
+        encode_value(lat - prev_lat, &mut encoded);
+        encode_value(lon - prev_lon, &mut encoded);
+
+        prev_lat = lat;
+        prev_lon = lon;
+    }
+
+    encoded
+}
+
+fn encode_value(value: i64, out: &mut String) {
+    let mut shifted = value << 1;
+    if value < 0 {
+        shifted = !shifted;
+    }
+
+    while shifted >= 0x20 {
+        let chunk = ((shifted & 0x1f) | 0x20) as u8 + 63;
+        out.push(chunk as char);
+        shifted >>= 5;
+    }
+    out.push((shifted as u8 + 63) as char);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_polyline_matches_reference_example() {
+        // The canonical example from Google's polyline algorithm docs.
+        let points = vec![(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)];
+        assert_eq!(encode_polyline(&points), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    }
+
+    #[test]
+    fn test_encode_polyline_empty_input() {
+        assert_eq!(encode_polyline(&[]), "");
+    }
+}
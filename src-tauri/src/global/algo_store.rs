@@ -1,11 +1,16 @@
 #![allow(dead_code)]
 
+use std::time::Duration;
+
+use crate::global::algo_telemetry::{AlgoTelemetry, FailureReason};
 use crate::graph::graph_ds::Graph;
 use crate::state_err_enums::ap::APResult;
 use crate::state_err_enums::diff_cen::DiffCenResult;
 use crate::state_err_enums::mincut::MinCutResult;
 use crate::state_err_enums::most_sim_timeline::MostSimTResult;
+use crate::state_err_enums::mst::MstResult;
 use crate::state_err_enums::pred_state::PredStateResult;
+use petgraph::graph::NodeIndex;
 
 pub struct AlgoStore {
     pub aps: APResult,
@@ -13,6 +18,8 @@ pub struct AlgoStore {
     pub diff_cent: DiffCenResult,
     pub most_sim_t: MostSimTResult,
     pub pred_state: PredStateResult,
+    pub mst: MstResult,
+    pub telemetry: AlgoTelemetry,
 }
 
 impl AlgoStore {
@@ -23,6 +30,8 @@ impl AlgoStore {
             diff_cent: DiffCenResult::Empty(true),
             most_sim_t: MostSimTResult::Empty(true),
             pred_state: PredStateResult::Empty(true),
+            mst: MstResult::Empty(true),
+            telemetry: AlgoTelemetry::new(),
         }
     }
 
@@ -46,23 +55,113 @@ impl AlgoStore {
         &self.pred_state
     }
 
-    pub fn set_aps(&mut self, aps: APResult) {
+    pub fn get_mst(&self) -> &MstResult {
+        &self.mst
+    }
+
+    pub fn set_aps(&mut self, aps: APResult, duration: Duration) {
+        match aps {
+            APResult::Success(_) => self.telemetry.aps.record_success(duration),
+            APResult::Empty(is_empty) => self.telemetry.aps.record_failure(empty_reason(is_empty)),
+        }
         self.aps = aps;
     }
 
-    pub fn set_mincut(&mut self, mincut: MinCutResult) {
+    pub fn set_mincut(&mut self, mincut: MinCutResult, duration: Duration) {
+        match mincut {
+            MinCutResult::Success(_) => self.telemetry.mincut.record_success(duration),
+            MinCutResult::Empty(is_empty) => {
+                self.telemetry.mincut.record_failure(empty_reason(is_empty))
+            }
+        }
         self.mincut = mincut;
     }
 
-    pub fn set_diff_cent(&mut self, diff_cent: DiffCenResult) {
+    pub fn set_diff_cent(&mut self, diff_cent: DiffCenResult, duration: Duration) {
+        match diff_cent {
+            DiffCenResult::Success(_) => self.telemetry.diff_cent.record_success(duration),
+            DiffCenResult::Empty(is_empty) => {
+                self.telemetry.diff_cent.record_failure(empty_reason(is_empty))
+            }
+        }
         self.diff_cent = diff_cent;
     }
 
-    pub fn set_most_sim_t(&mut self, most_sim_t: Graph) {
+    pub fn set_most_sim_t(&mut self, most_sim_t: Graph, duration: Duration) {
+        self.telemetry.most_sim_t.record_success(duration);
         self.most_sim_t = MostSimTResult::Success(most_sim_t);
     }
 
-    pub fn set_pred_state(&mut self, pred_state: Graph) {
+    pub fn set_pred_state(&mut self, pred_state: Graph, duration: Duration) {
+        self.telemetry.pred_state.record_success(duration);
         self.pred_state = PredStateResult::Success(pred_state);
     }
+
+    pub fn set_mst(&mut self, mst: Vec<(NodeIndex, NodeIndex, f64)>) {
+        self.mst = MstResult::Success(mst);
+    }
+
+    pub fn get_telemetry(&self) -> &AlgoTelemetry {
+        &self.telemetry
+    }
+}
+
+/// `Empty(true)` means the graph itself was empty; `Empty(false)` covers the
+/// other reasons an analysis can come back without a result.
+fn empty_reason(is_empty_graph: bool) -> FailureReason {
+    if is_empty_graph {
+        FailureReason::EmptyGraph
+    } else {
+        FailureReason::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_aps_records_success_telemetry() {
+        let mut store = AlgoStore::new();
+        store.set_aps(
+            APResult::Success(vec![NodeIndex::new(0)]),
+            Duration::from_millis(5),
+        );
+        assert_eq!(store.telemetry.aps.invocation_count, 1);
+        assert_eq!(store.telemetry.aps.last_duration, Some(Duration::from_millis(5)));
+        assert_eq!(store.telemetry.aps.last_failure, None);
+    }
+
+    #[test]
+    fn test_set_mincut_records_empty_graph_failure_telemetry() {
+        let mut store = AlgoStore::new();
+        store.set_mincut(MinCutResult::Empty(true), Duration::from_millis(1));
+        assert_eq!(store.telemetry.mincut.invocation_count, 1);
+        assert_eq!(store.telemetry.mincut.last_failure, Some(FailureReason::EmptyGraph));
+    }
+
+    #[test]
+    fn test_set_diff_cent_records_other_failure_when_not_an_empty_graph() {
+        let mut store = AlgoStore::new();
+        store.set_diff_cent(DiffCenResult::Empty(false), Duration::from_millis(1));
+        assert_eq!(store.telemetry.diff_cent.last_failure, Some(FailureReason::Other));
+    }
+
+    #[test]
+    fn test_set_most_sim_t_and_pred_state_record_success_telemetry() {
+        let mut store = AlgoStore::new();
+        store.set_most_sim_t(Graph::new(), Duration::from_millis(2));
+        store.set_pred_state(Graph::new(), Duration::from_millis(3));
+        assert_eq!(store.telemetry.most_sim_t.invocation_count, 1);
+        assert_eq!(store.telemetry.pred_state.invocation_count, 1);
+    }
+
+    #[test]
+    fn test_get_telemetry_reflects_latest_recorded_run() {
+        let mut store = AlgoStore::new();
+        store.set_aps(APResult::Empty(true), Duration::from_millis(1));
+        let snapshot = store.get_telemetry().snapshot();
+        let aps_metrics = snapshot.iter().find(|(name, _)| *name == "aps").unwrap().1;
+        assert_eq!(aps_metrics.invocation_count, 1);
+    }
 }
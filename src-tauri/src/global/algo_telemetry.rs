@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+/// Why an analysis's last run did not produce a usable result, mirroring the
+/// status information the `*Result` enums in `state_err_enums` already
+/// encode, but categorized so the client can distinguish "nothing to
+/// analyze yet" from an actual problem worth surfacing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureReason {
+    EmptyGraph,
+    Disconnected,
+    Timeout,
+    Other,
+}
+
+/// Invocation count, last successful duration, and last failure reason for a
+/// single analysis.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlgoMetrics {
+    pub invocation_count: u64,
+    pub last_duration: Option<Duration>,
+    pub last_failure: Option<FailureReason>,
+}
+
+impl AlgoMetrics {
+    pub fn record_success(&mut self, duration: Duration) {
+        self.invocation_count += 1;
+        self.last_duration = Some(duration);
+        self.last_failure = None;
+    }
+
+    pub fn record_failure(&mut self, reason: FailureReason) {
+        self.invocation_count += 1;
+        self.last_failure = Some(reason);
+    }
+}
+
+/// Per-analysis telemetry for the five `AlgoStore` analyses, so the client
+/// can tell which analyses are actually being triggered and which are
+/// silently returning `Empty` without instrumenting each call site.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlgoTelemetry {
+    pub aps: AlgoMetrics,
+    pub mincut: AlgoMetrics,
+    pub diff_cent: AlgoMetrics,
+    pub most_sim_t: AlgoMetrics,
+    pub pred_state: AlgoMetrics,
+}
+
+impl AlgoTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshots every analysis's metrics at once, keyed by analysis name.
+    pub fn snapshot(&self) -> [(&'static str, AlgoMetrics); 5] {
+        [
+            ("aps", self.aps),
+            ("mincut", self.mincut),
+            ("diff_cent", self.diff_cent),
+            ("most_sim_t", self.most_sim_t),
+            ("pred_state", self.pred_state),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_failure_preserves_last_success_duration() {
+        let mut metrics = AlgoMetrics::default();
+        metrics.record_success(Duration::from_millis(50));
+        metrics.record_failure(FailureReason::EmptyGraph);
+
+        assert_eq!(metrics.invocation_count, 2);
+        assert_eq!(metrics.last_duration, Some(Duration::from_millis(50)));
+        assert_eq!(metrics.last_failure, Some(FailureReason::EmptyGraph));
+    }
+
+    #[test]
+    fn test_record_success_clears_last_failure() {
+        let mut metrics = AlgoMetrics::default();
+        metrics.record_failure(FailureReason::Timeout);
+        metrics.record_success(Duration::from_millis(10));
+
+        assert_eq!(metrics.last_failure, None);
+        assert_eq!(metrics.last_duration, Some(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_snapshot_returns_all_five_tracked_analyses() {
+        let telemetry = AlgoTelemetry::new();
+        let snapshot = telemetry.snapshot();
+        let names: Vec<&str> = snapshot.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            vec!["aps", "mincut", "diff_cent", "most_sim_t", "pred_state"]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod algo_store;
+pub mod algo_telemetry;
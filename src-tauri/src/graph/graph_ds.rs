@@ -0,0 +1,440 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use petgraph::graph::{EdgeIndex, NodeIndex, UnGraph};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::aux_data_structures::union_find::UnionFind;
+use crate::aux_functions::edge_factory::{Edge, DEFAULT_DISTANCE_WEIGHT, DISTANCE_NORMALIZATION_M};
+use crate::aux_functions::take_snapshot::total_distance;
+
+/// The mesh topology, as inferred from observed SNR readings between nodes.
+///
+/// Nodes are keyed by their stringified node ID; `node_idx_map` lets callers
+/// look a node up by that ID instead of threading `NodeIndex`es around.
+/// `positions` holds each node's last-known lat/long in degrees, when known,
+/// so analyses like [`Graph::shortest_path`] can use a geographic heuristic.
+/// `link_metrics` holds each edge's raw distance and SNR, keyed by its
+/// endpoints, alongside the combined weight stored on the petgraph edge
+/// itself, so consumers like GeoJSON export can report the underlying
+/// measurements rather than just the derived routing cost. `distance_weight`
+/// mirrors the `distance_weight` passed to `edge_factory` when this graph's
+/// edges were built, so [`Graph::shortest_path`]'s heuristic can be scaled to
+/// match and stay admissible.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    pub graph: UnGraph<String, f64>,
+    node_idx_map: HashMap<String, NodeIndex>,
+    positions: HashMap<NodeIndex, (f64, f64)>,
+    link_metrics: HashMap<(NodeIndex, NodeIndex), (f64, f64)>,
+    distance_weight: f64,
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph {
+            graph: UnGraph::new_undirected(),
+            node_idx_map: HashMap::new(),
+            positions: HashMap::new(),
+            link_metrics: HashMap::new(),
+            distance_weight: DEFAULT_DISTANCE_WEIGHT,
+        }
+    }
+
+    /// Sets the `distance_weight` that was used to build this graph's edge
+    /// weights (see `edge_factory::edge_factory`), so `shortest_path`'s
+    /// heuristic can scale the great-circle estimate to match. Defaults to
+    /// `1.0`, matching `edge_factory`'s own default.
+    pub fn set_distance_weight(&mut self, distance_weight: f64) {
+        self.distance_weight = distance_weight;
+    }
+
+    pub fn add_node(&mut self, name: String) -> NodeIndex {
+        let idx = self.graph.add_node(name.clone());
+        self.node_idx_map.insert(name, idx);
+        idx
+    }
+
+    pub fn contains_node(&self, name: String) -> bool {
+        self.node_idx_map.contains_key(&name)
+    }
+
+    pub fn get_node_idx(&self, name: String) -> NodeIndex {
+        *self
+            .node_idx_map
+            .get(&name)
+            .unwrap_or_else(|| panic!("node {} not found in graph", name))
+    }
+
+    /// Records `name`'s GPS position, in degrees, for use by heuristics like
+    /// the one in [`Graph::shortest_path`]. Nodes with no reported GPS fix
+    /// simply never get an entry here.
+    pub fn set_node_position(&mut self, name: String, latitude: f64, longitude: f64) {
+        let idx = self.get_node_idx(name);
+        self.positions.insert(idx, (latitude, longitude));
+    }
+
+    pub fn get_node_position(&self, name: String) -> Option<(f64, f64)> {
+        let idx = self.get_node_idx(name);
+        self.positions.get(&idx).copied()
+    }
+
+    pub fn add_edge_from_struct(&mut self, edge: Edge) {
+        let key = Self::link_key(edge.u, edge.v);
+        match self.graph.find_edge(edge.u, edge.v) {
+            Some(existing) => {
+                // A weaker (more costly) reading of the same link supersedes
+                // an optimistic one, so routing never relies on a link that's
+                // actually worse than it first appeared.
+                if edge.weight > self.graph[existing] {
+                    self.graph[existing] = edge.weight;
+                    self.link_metrics.insert(key, (edge.distance_m, edge.snr));
+                }
+            }
+            None => {
+                self.graph.add_edge(edge.u, edge.v, edge.weight);
+                self.link_metrics.insert(key, (edge.distance_m, edge.snr));
+            }
+        }
+    }
+
+    /// Returns the `(distance_m, snr)` recorded for the link between `u` and
+    /// `v`, if one was materialized.
+    pub fn get_link_metrics(&self, u: NodeIndex, v: NodeIndex) -> Option<(f64, f64)> {
+        self.link_metrics.get(&Self::link_key(u, v)).copied()
+    }
+
+    fn link_key(u: NodeIndex, v: NodeIndex) -> (NodeIndex, NodeIndex) {
+        if u <= v {
+            (u, v)
+        } else {
+            (v, u)
+        }
+    }
+
+    pub fn get_order(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    pub fn get_edge_weight(
+        &self,
+        node_1: String,
+        node_2: String,
+        _edge_idx: Option<EdgeIndex>,
+        _directed: Option<bool>,
+    ) -> f64 {
+        let idx_1 = self.get_node_idx(node_1);
+        let idx_2 = self.get_node_idx(node_2);
+        self.graph
+            .find_edge(idx_1, idx_2)
+            .map(|edge| self.graph[edge])
+            .unwrap_or(f64::INFINITY)
+    }
+
+    /// Finds the cheapest route from `src` to `dst` using A* over the
+    /// `edge_factory` weights, returning the ordered node-ID path and its
+    /// total cost. Returns `None` when no path exists.
+    ///
+    /// The heuristic is the great-circle distance from the frontier node to
+    /// `dst`, normalized the same way `edge_factory` normalizes edge
+    /// distances and scaled by `distance_weight` (see
+    /// [`Graph::set_distance_weight`]). It does not overestimate the true
+    /// remaining cost as long as every edge's weight includes at least that
+    /// scaled great-circle distance between its endpoints, as `edge_factory`
+    /// weights built from GPS distances do; edges whose weight falls below it
+    /// (e.g. from an SNR-estimated distance) can make the returned route
+    /// suboptimal.
+    /// When either the frontier node or `dst` has no known position, the
+    /// heuristic falls back to 0, which degrades the search to plain
+    /// Dijkstra rather than risking an inadmissible estimate.
+    pub fn shortest_path(&self, src: String, dst: String) -> Option<(Vec<String>, f64)> {
+        if !self.contains_node(src.clone()) || !self.contains_node(dst.clone()) {
+            return None;
+        }
+
+        let src_idx = self.get_node_idx(src);
+        let dst_idx = self.get_node_idx(dst);
+
+        let mut distances: HashMap<NodeIndex, f64> = HashMap::new();
+        let mut predecessors: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut open: BinaryHeap<Frontier> = BinaryHeap::new();
+
+        distances.insert(src_idx, 0.0);
+        open.push(Frontier {
+            idx: src_idx,
+            f_score: self.heuristic(src_idx, dst_idx),
+        });
+
+        while let Some(Frontier { idx, .. }) = open.pop() {
+            if idx == dst_idx {
+                return Some((self.reconstruct_path(&predecessors, dst_idx), distances[&dst_idx]));
+            }
+
+            let g_score = *distances.get(&idx).unwrap_or(&f64::INFINITY);
+
+            for edge in self.graph.edges(idx) {
+                let neighbor = edge.target();
+                let tentative_g = g_score + *edge.weight();
+                if tentative_g < *distances.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                    distances.insert(neighbor, tentative_g);
+                    predecessors.insert(neighbor, idx);
+                    open.push(Frontier {
+                        idx: neighbor,
+                        f_score: tentative_g + self.heuristic(neighbor, dst_idx),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn heuristic(&self, from: NodeIndex, to: NodeIndex) -> f64 {
+        match (self.positions.get(&from), self.positions.get(&to)) {
+            (Some(&(lat_1, lon_1)), Some(&(lat_2, lon_2))) => {
+                self.distance_weight
+                    * (total_distance(lat_1, lon_1, 0.0, lat_2, lon_2, 0.0) / DISTANCE_NORMALIZATION_M)
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn reconstruct_path(
+        &self,
+        predecessors: &HashMap<NodeIndex, NodeIndex>,
+        dst_idx: NodeIndex,
+    ) -> Vec<String> {
+        let mut path = vec![dst_idx];
+        let mut current = dst_idx;
+        while let Some(&prev) = predecessors.get(&current) {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        path.into_iter().map(|idx| self.graph[idx].clone()).collect()
+    }
+
+    /// Computes the cheapest connected backbone via Kruskal's algorithm:
+    /// edges are sorted ascending by weight and accepted greedily whenever
+    /// their endpoints fall in different union-find components, stopping
+    /// once `order - 1` edges have been accepted. When the graph is
+    /// disconnected, this naturally yields a spanning forest rather than
+    /// failing, since Kruskal only ever merges reachable components.
+    pub fn minimum_spanning_tree(&self) -> Vec<(NodeIndex, NodeIndex, f64)> {
+        let mut edges: Vec<(NodeIndex, NodeIndex, f64)> = self
+            .graph
+            .edge_references()
+            .map(|edge| (edge.source(), edge.target(), *edge.weight()))
+            .collect();
+        edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+
+        let mut union_find = UnionFind::new(self.graph.node_count());
+        let mut mst = Vec::new();
+        let target_edge_count = self.get_order().saturating_sub(1);
+
+        for (u, v, weight) in edges {
+            if mst.len() == target_edge_count {
+                break;
+            }
+            if union_find.union(u.index(), v.index()) {
+                mst.push((u, v, weight));
+            }
+        }
+        mst
+    }
+}
+
+/// A node on the A* open set, ordered by ascending `f_score` so `BinaryHeap`
+/// (a max-heap) pops the most promising frontier node first.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Frontier {
+    idx: NodeIndex,
+    f_score: f64,
+}
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f_score
+            .partial_cmp(&self.f_score)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_graph() -> Graph {
+        // a(0,0) -- b(0,0.001) -- c(0,0.002), plus a direct but costlier a-c
+        // edge. Hops are ~111m, so the heuristic (~0.11 per hop) stays below
+        // every edge weight.
+        let mut graph = Graph::new();
+        graph.add_node("a".to_string());
+        graph.add_node("b".to_string());
+        graph.add_node("c".to_string());
+        graph.set_node_position("a".to_string(), 0.0, 0.0);
+        graph.set_node_position("b".to_string(), 0.0, 0.001);
+        graph.set_node_position("c".to_string(), 0.0, 0.002);
+
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("a".to_string()),
+            v: graph.get_node_idx("b".to_string()),
+            distance_m: 0.0,
+            snr: 0.0,
+            weight: 1.0,
+        });
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("b".to_string()),
+            v: graph.get_node_idx("c".to_string()),
+            distance_m: 0.0,
+            snr: 0.0,
+            weight: 1.0,
+        });
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("a".to_string()),
+            v: graph.get_node_idx("c".to_string()),
+            distance_m: 0.0,
+            snr: 0.0,
+            weight: 10.0,
+        });
+        graph
+    }
+
+    #[test]
+    fn test_shortest_path_prefers_cheaper_multi_hop_route() {
+        let graph = line_graph();
+        let (path, cost) = graph
+            .shortest_path("a".to_string(), "c".to_string())
+            .expect("path should exist");
+        assert_eq!(path, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(cost, 2.0);
+    }
+
+    #[test]
+    fn test_shortest_path_unreachable_returns_none() {
+        let mut graph = line_graph();
+        graph.add_node("isolated".to_string());
+        assert!(graph
+            .shortest_path("a".to_string(), "isolated".to_string())
+            .is_none());
+    }
+
+    #[test]
+    fn test_shortest_path_falls_back_to_dijkstra_without_positions() {
+        let mut graph = Graph::new();
+        graph.add_node("a".to_string());
+        graph.add_node("b".to_string());
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("a".to_string()),
+            v: graph.get_node_idx("b".to_string()),
+            distance_m: 0.0,
+            snr: 0.0,
+            weight: 1.0,
+        });
+        let (path, cost) = graph
+            .shortest_path("a".to_string(), "b".to_string())
+            .expect("path should exist");
+        assert_eq!(path, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(cost, 1.0);
+    }
+
+    #[test]
+    fn test_shortest_path_scales_heuristic_by_distance_weight() {
+        // s-g is a short direct hop with bad radio quality (cost 1.05); the
+        // s-m-g detour is geographically longer but has perfect radio
+        // quality on both hops (cost 0.2 + 0.2 = 0.4), so it's optimal.
+        // With `distance_weight` left at the `edge_factory` default of 1.0,
+        // scaling the heuristic down to match this graph's actual
+        // `distance_weight` of 0.1 is required for the heuristic to stay
+        // admissible — otherwise A* settles for the suboptimal direct hop.
+        let mut graph = Graph::new();
+        graph.add_node("s".to_string());
+        graph.add_node("m".to_string());
+        graph.add_node("g".to_string());
+        graph.set_node_position("s".to_string(), 0.0, 0.0);
+        graph.set_node_position("g".to_string(), 0.004_492, 0.0); // ~500m from s
+        graph.set_node_position("m".to_string(), 0.022_458, 0.0); // ~2000m from g
+        graph.set_distance_weight(0.1);
+
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("s".to_string()),
+            v: graph.get_node_idx("g".to_string()),
+            distance_m: 500.0,
+            snr: 0.0,
+            weight: 1.05,
+        });
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("s".to_string()),
+            v: graph.get_node_idx("m".to_string()),
+            distance_m: 2000.0,
+            snr: 1.0,
+            weight: 0.2,
+        });
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("m".to_string()),
+            v: graph.get_node_idx("g".to_string()),
+            distance_m: 2000.0,
+            snr: 1.0,
+            weight: 0.2,
+        });
+
+        let (path, cost) = graph
+            .shortest_path("s".to_string(), "g".to_string())
+            .expect("path should exist");
+        assert_eq!(path, vec!["s".to_string(), "m".to_string(), "g".to_string()]);
+        assert!((cost - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mst_drops_the_costlier_redundant_edge() {
+        let graph = line_graph();
+        let mst = graph.minimum_spanning_tree();
+        assert_eq!(mst.len(), 2);
+        let total_weight: f64 = mst.iter().map(|(_, _, weight)| weight).sum();
+        assert_eq!(total_weight, 2.0);
+    }
+
+    #[test]
+    fn test_mst_on_disconnected_graph_yields_a_forest() {
+        let mut graph = Graph::new();
+        graph.add_node("a".to_string());
+        graph.add_node("b".to_string());
+        graph.add_node("c".to_string());
+        graph.add_node("d".to_string());
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("a".to_string()),
+            v: graph.get_node_idx("b".to_string()),
+            distance_m: 0.0,
+            snr: 0.0,
+            weight: 1.0,
+        });
+        graph.add_edge_from_struct(Edge {
+            u: graph.get_node_idx("c".to_string()),
+            v: graph.get_node_idx("d".to_string()),
+            distance_m: 0.0,
+            snr: 0.0,
+            weight: 1.0,
+        });
+        let mst = graph.minimum_spanning_tree();
+        assert_eq!(mst.len(), 2);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod aux_data_structures;
+pub mod aux_functions;
+pub mod export;
+pub mod global;
+pub mod graph;
+pub mod mesh;
+pub mod mocks;
+pub mod state_err_enums;
@@ -0,0 +1,10 @@
+use app::protobufs;
+
+/// A mesh node as tracked by the client, combining its last-known protobuf
+/// `NodeInfo` with the rolling history of metrics reported about it.
+#[derive(Debug, Clone)]
+pub struct MeshNode {
+    pub device_metrics: Vec<protobufs::DeviceMetrics>,
+    pub environment_metrics: Vec<protobufs::EnvironmentMetrics>,
+    pub data: protobufs::NodeInfo,
+}
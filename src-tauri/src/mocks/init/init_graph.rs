@@ -1,19 +1,26 @@
 use crate::aux_data_structures::neighbor_info::{Neighbor, NeighborInfo};
 use crate::aux_functions::conversion_factors::{
-    ALT_CONVERSION_FACTOR, HANOVER_LAT_PREFIX, HANOVER_LON_PREFIX, LAT_CONVERSION_FACTOR,
-    LON_CONVERSION_FACTOR,
+    ALT_CONVERSION_FACTOR, LAT_CONVERSION_FACTOR, LON_CONVERSION_FACTOR,
 };
-use crate::aux_functions::edge_factory::edge_factory;
+use crate::aux_functions::edge_factory::{edge_factory, DEFAULT_DISTANCE_WEIGHT};
+use crate::aux_functions::path_loss::{estimate_distance_from_snr, PathLossParams};
 use crate::aux_functions::take_snapshot::total_distance;
 use crate::graph::graph_ds::Graph;
 use crate::mesh::device::MeshNode;
 use app::protobufs;
 use petgraph::graph::NodeIndex;
-use std::collections::HashMap;
+use rstar::{RTree, RTreeObject, AABB};
+use std::collections::{HashMap, HashSet};
+
+/// Mean radius of the earth, in meters, used to project node positions onto
+/// a local tangent plane for the R-tree range query.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
 
 pub fn init_graph(
     mut snr_hashmap: HashMap<(u32, u32), (f64, u64)>,
     mut loc_hashmap: HashMap<u32, MeshNode>,
+    max_link_distance_m: Option<f64>,
+    distance_weight: Option<f64>,
 ) -> Graph {
     // Traverse the array of packets once, adding nodes and edges to our lists
     let mut graph = Graph::new();
@@ -22,9 +29,28 @@ pub fn init_graph(
     let mut edge_distances = Vec::<f64>::new();
     let mut edge_radio_quality = Vec::<f64>::new();
 
+    // When a radius is given, only materialize edges between nodes the
+    // R-tree says are geographically close enough to plausibly be in radio
+    // range; this keeps edge construction to O(n log n) instead of scoring
+    // every observed SNR pair regardless of distance.
+    let plausible_pairs = max_link_distance_m.map(|radius| plausible_neighbor_pairs(&loc_hashmap, radius));
+
     for neighbor_pair in snr_hashmap {
         let node_id = neighbor_pair.0 .0;
         let neighbor_id = neighbor_pair.0 .1;
+        // The R-tree filter only has an opinion about nodes it could place
+        // on the tangent plane. When either endpoint has no GPS fix, there's
+        // no geographic distance to bound, so let the pair through and leave
+        // it to the SNR path-loss fallback below to estimate a distance
+        // instead of silently dropping the node from the graph.
+        if let Some(pairs) = &plausible_pairs {
+            if has_position(&loc_hashmap, node_id)
+                && has_position(&loc_hashmap, neighbor_id)
+                && !pairs.contains(&unordered_pair(node_id, neighbor_id))
+            {
+                continue;
+            }
+        }
         add_node_to_graph_if_not_exists(&mut graph, node_id);
         add_node_to_graph_if_not_exists(&mut graph, neighbor_id);
         let node_idx = graph.get_node_idx(node_id.to_string());
@@ -32,7 +58,11 @@ pub fn init_graph(
         let snr = neighbor_pair.1 .0;
         let node_loc = loc_hashmap.get(&node_id).unwrap();
         let neighbor_loc = loc_hashmap.get(&neighbor_id).unwrap();
-        let distance = get_distance(node_loc.clone(), neighbor_loc.clone());
+        // Nodes with no GPS fix fall back to an SNR-derived distance
+        // estimate instead of panicking, so topology analysis still runs on
+        // meshes where many nodes never report coordinates.
+        let distance = get_distance(node_loc.clone(), neighbor_loc.clone())
+            .unwrap_or_else(|| estimate_distance_from_snr(snr, &PathLossParams::default()));
         edge_left_endpoints.push(node_idx);
         edge_right_endpoints.push(neighbor_idx);
         edge_distances.push(distance);
@@ -45,9 +75,13 @@ pub fn init_graph(
         edge_right_endpoints,
         edge_distances,
         edge_radio_quality,
-        None,
+        distance_weight,
         None,
     );
+    // Threaded through so `shortest_path`'s heuristic, which scales by this
+    // same factor, stays admissible for whatever `distance_weight` these
+    // edges were actually built with.
+    graph.set_distance_weight(distance_weight.unwrap_or(DEFAULT_DISTANCE_WEIGHT));
     // Add the edges to the graph
     for edge in edges {
         graph.add_edge_from_struct(edge);
@@ -55,6 +89,91 @@ pub fn init_graph(
     graph
 }
 
+/// A node's position, projected onto a local equirectangular tangent plane
+/// around the snapshot's centroid so a Euclidean R-tree range query
+/// approximates great-circle distance closely enough at mesh-radio ranges.
+struct ProjectedNode {
+    node_id: u32,
+    point: [f64; 2],
+}
+
+impl RTreeObject for ProjectedNode {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point(self.point)
+    }
+}
+
+/// Returns every unordered pair of nodes whose projected positions are
+/// within `max_link_distance_m` of each other, found via a bounded R-tree
+/// range query per node rather than scoring every pair.
+fn plausible_neighbor_pairs(
+    loc_hashmap: &HashMap<u32, MeshNode>,
+    max_link_distance_m: f64,
+) -> HashSet<(u32, u32)> {
+    let positioned: Vec<(u32, f64, f64)> = loc_hashmap
+        .iter()
+        .filter_map(|(&node_id, mesh_node)| {
+            let position = mesh_node.data.position?;
+            Some((
+                node_id,
+                position.latitude_i as f64 * LAT_CONVERSION_FACTOR,
+                position.longitude_i as f64 * LON_CONVERSION_FACTOR,
+            ))
+        })
+        .collect();
+
+    if positioned.is_empty() {
+        return HashSet::new();
+    }
+
+    let centroid_lat =
+        positioned.iter().map(|(_, lat, _)| lat).sum::<f64>() / positioned.len() as f64;
+    let centroid_lon =
+        positioned.iter().map(|(_, _, lon)| lon).sum::<f64>() / positioned.len() as f64;
+
+    let tree = RTree::bulk_load(
+        positioned
+            .iter()
+            .map(|&(node_id, lat, lon)| ProjectedNode {
+                node_id,
+                point: equirectangular_project(lat, lon, centroid_lat, centroid_lon),
+            })
+            .collect(),
+    );
+
+    let mut pairs = HashSet::new();
+    for node in tree.iter() {
+        for neighbor in tree.locate_within_distance(node.point, max_link_distance_m.powi(2)) {
+            if neighbor.node_id != node.node_id {
+                pairs.insert(unordered_pair(node.node_id, neighbor.node_id));
+            }
+        }
+    }
+    pairs
+}
+
+fn equirectangular_project(lat: f64, lon: f64, centroid_lat: f64, centroid_lon: f64) -> [f64; 2] {
+    let x = EARTH_RADIUS_M * (lon - centroid_lon).to_radians() * centroid_lat.to_radians().cos();
+    let y = EARTH_RADIUS_M * (lat - centroid_lat).to_radians();
+    [x, y]
+}
+
+fn unordered_pair(a: u32, b: u32) -> (u32, u32) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+fn has_position(loc_hashmap: &HashMap<u32, MeshNode>, node_id: u32) -> bool {
+    loc_hashmap
+        .get(&node_id)
+        .is_some_and(|mesh_node| mesh_node.data.position.is_some())
+}
+
 pub fn add_node_to_graph_if_not_exists(graph: &mut Graph, node_id: u32) {
     let name: String = node_id.to_string();
     if !graph.contains_node(name.clone()) {
@@ -68,20 +187,21 @@ pub fn add_node_to_graph_if_not_exists(graph: &mut Graph, node_id: u32) {
 * Conversion function:
 * Lat/Long: 1e-7 conversion from int to floating point degrees; see mesh.proto
 * Altitude: in meters above sea level, no conversion needed
+*
+* Returns None when either node has no GPS fix, so callers can fall back to
+* an SNR-derived estimate instead of panicking.
 */
-pub fn get_distance(node_1: MeshNode, node_2: MeshNode) -> f64 {
-    let node_1_data = node_1.data;
-    let node_2_data = node_2.data;
-    let node_1_pos = node_1_data.position.unwrap();
-    let node_2_pos = node_2_data.position.unwrap();
-    total_distance(
+pub fn get_distance(node_1: MeshNode, node_2: MeshNode) -> Option<f64> {
+    let node_1_pos = node_1.data.position?;
+    let node_2_pos = node_2.data.position?;
+    Some(total_distance(
         node_1_pos.latitude_i as f64 * LAT_CONVERSION_FACTOR,
         node_1_pos.longitude_i as f64 * LON_CONVERSION_FACTOR,
         node_1_pos.altitude as f64 * ALT_CONVERSION_FACTOR,
         node_2_pos.latitude_i as f64 * LAT_CONVERSION_FACTOR,
         node_2_pos.longitude_i as f64 * LON_CONVERSION_FACTOR,
         node_2_pos.altitude as f64 * ALT_CONVERSION_FACTOR,
-    )
+    ))
 }
 
 #[cfg(test)]
@@ -200,7 +320,7 @@ mod tests {
         snr_hashmap.insert((2, 3), (0.9, 0));
         snr_hashmap.insert((2, 4), (0.9, 0));
         snr_hashmap.insert((3, 4), (0.9, 0));
-        let graph = init_graph(snr_hashmap, loc_hashmap);
+        let graph = init_graph(snr_hashmap, loc_hashmap, None, None);
         // Check that the graph has the correct number of nodes
         assert_eq!(graph.get_order(), 4);
         // Check that the graph has the correct number of edges
@@ -307,7 +427,7 @@ mod tests {
         loc_hashmap.insert(2, meshnode_2);
         snr_hashmap.insert((1, 2), (0.1, 100));
         snr_hashmap.insert((2, 1), (0.9, 0));
-        let mut graph = init_graph(snr_hashmap, loc_hashmap);
+        let mut graph = init_graph(snr_hashmap, loc_hashmap, None, None);
         // Check that the graph has the correct number of edges
         assert_eq!(graph.get_size(), 1);
         // Check the edge weights to check that they are both the weight of the 1-2 edge, which has neighbor 2's SNR
@@ -321,4 +441,73 @@ mod tests {
         // The correct weight should a sum of the two distances normalized w 0.1 radio quality, which is this float
         assert_eq!(first_edge_weight, 1.0);
     }
+
+    fn meshnode_without_position(num: u32) -> MeshNode {
+        MeshNode {
+            device_metrics: vec![],
+            environment_metrics: vec![],
+            data: protobufs::NodeInfo {
+                num,
+                user: Some(generate_test_user()),
+                position: None,
+                snr: 0.0,
+                last_heard: 0,
+                device_metrics: Some(generate_zeroed_device_metrics()),
+            },
+        }
+    }
+
+    fn meshnode_with_position(num: u32) -> MeshNode {
+        MeshNode {
+            device_metrics: vec![],
+            environment_metrics: vec![],
+            data: protobufs::NodeInfo {
+                num,
+                user: Some(generate_test_user()),
+                position: Some(generate_zeroed_position()),
+                snr: 0.0,
+                last_heard: 0,
+                device_metrics: Some(generate_zeroed_device_metrics()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_get_distance_returns_none_without_position() {
+        let positioned = meshnode_with_position(1);
+        let unpositioned = meshnode_without_position(2);
+        assert!(get_distance(positioned, unpositioned).is_none());
+    }
+
+    #[test]
+    fn test_init_graph_falls_back_to_snr_distance_for_unpositioned_node() {
+        let mut loc_hashmap: HashMap<u32, MeshNode> = HashMap::new();
+        let mut snr_hashmap: HashMap<(u32, u32), (f64, u64)> = HashMap::new();
+        loc_hashmap.insert(1, meshnode_with_position(1));
+        loc_hashmap.insert(2, meshnode_without_position(2));
+        snr_hashmap.insert((1, 2), (0.5, 0));
+
+        let graph = init_graph(snr_hashmap, loc_hashmap, None, None);
+        // The unpositioned node still ends up in the graph, instead of being
+        // silently dropped because `get_distance` couldn't compute a
+        // great-circle distance for it.
+        assert_eq!(graph.get_order(), 2);
+        assert_eq!(graph.get_size(), 1);
+    }
+
+    #[test]
+    fn test_init_graph_keeps_unpositioned_node_under_bounded_radius() {
+        let mut loc_hashmap: HashMap<u32, MeshNode> = HashMap::new();
+        let mut snr_hashmap: HashMap<(u32, u32), (f64, u64)> = HashMap::new();
+        loc_hashmap.insert(1, meshnode_with_position(1));
+        loc_hashmap.insert(2, meshnode_without_position(2));
+        snr_hashmap.insert((1, 2), (0.5, 0));
+
+        // Even with a bounded search radius, a node the R-tree can't place
+        // (no GPS fix) must still get an edge via the SNR fallback rather
+        // than being filtered out entirely.
+        let graph = init_graph(snr_hashmap, loc_hashmap, Some(1000.0), None);
+        assert_eq!(graph.get_order(), 2);
+        assert_eq!(graph.get_size(), 1);
+    }
 }
@@ -0,0 +1,8 @@
+use petgraph::graph::NodeIndex;
+
+/// Outcome of an articulation-point analysis run.
+#[derive(Debug, Clone)]
+pub enum APResult {
+    Success(Vec<NodeIndex>),
+    Empty(bool),
+}
@@ -0,0 +1,9 @@
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+
+/// Outcome of a diffusion-centrality analysis run.
+#[derive(Debug, Clone)]
+pub enum DiffCenResult {
+    Success(HashMap<NodeIndex, f64>),
+    Empty(bool),
+}
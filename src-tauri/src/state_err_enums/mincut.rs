@@ -0,0 +1,8 @@
+use petgraph::graph::NodeIndex;
+
+/// Outcome of a minimum-cut analysis run.
+#[derive(Debug, Clone)]
+pub enum MinCutResult {
+    Success(Vec<(NodeIndex, NodeIndex)>),
+    Empty(bool),
+}
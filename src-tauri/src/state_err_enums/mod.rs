@@ -0,0 +1,6 @@
+pub mod ap;
+pub mod diff_cen;
+pub mod mincut;
+pub mod mst;
+pub mod most_sim_timeline;
+pub mod pred_state;
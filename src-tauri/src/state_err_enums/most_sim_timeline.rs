@@ -0,0 +1,8 @@
+use crate::graph::graph_ds::Graph;
+
+/// Outcome of a most-similar-timeline analysis run.
+#[derive(Debug, Clone)]
+pub enum MostSimTResult {
+    Success(Graph),
+    Empty(bool),
+}
@@ -0,0 +1,10 @@
+use petgraph::graph::NodeIndex;
+
+/// Outcome of a minimum-spanning-tree (backbone) analysis run. `Success`
+/// holds the accepted edges as `(u, v, weight)` triples; for a disconnected
+/// graph this is a spanning forest rather than a single tree.
+#[derive(Debug, Clone)]
+pub enum MstResult {
+    Success(Vec<(NodeIndex, NodeIndex, f64)>),
+    Empty(bool),
+}
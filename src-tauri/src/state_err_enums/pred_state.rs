@@ -0,0 +1,8 @@
+use crate::graph::graph_ds::Graph;
+
+/// Outcome of a predicted-state analysis run.
+#[derive(Debug, Clone)]
+pub enum PredStateResult {
+    Success(Graph),
+    Empty(bool),
+}